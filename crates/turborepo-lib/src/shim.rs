@@ -31,29 +31,65 @@ static TURBO_PURE_OUTPUT_ARGS: [&str; 6] = [
 
 static SUPPORTS_SKIP_INFER_SEMVER: &str = ">=1.7.0-canary.0";
 
+// Opt-out for the global/local version skew warning emitted before
+// delegating to the local turbo binary.
+static TURBO_IGNORE_VERSION_SKEW_ENV_VAR: &str = "TURBO_IGNORE_VERSION_SKEW";
+// Opts into the `--shim-debug` trace without passing the flag explicitly.
+static TURBO_SHIM_DEBUG_ENV_VAR: &str = "TURBO_SHIM_DEBUG";
+// Overrides `DEFAULT_ALLOWED_MINOR_VERSION_SKEW` with a custom minor version
+// range.
+static TURBO_VERSION_SKEW_MINOR_RANGE_ENV_VAR: &str = "TURBO_VERSION_SKEW_MINOR_RANGE";
+// How many minor versions (within the same major) the global and local
+// turbo are allowed to drift apart before we warn, absent
+// `TURBO_VERSION_SKEW_MINOR_RANGE`. Any major version mismatch always warns.
+static DEFAULT_ALLOWED_MINOR_VERSION_SKEW: u64 = 5;
+
+// All package managers we know how to detect workspaces for, in the order
+// we probe them. Managers with their own lockfile/workspace config (Pnpm,
+// Yarn, Bun) are probed before the generic `Npm`, which otherwise would
+// claim any repo that merely has a `workspaces` field in `package.json` -
+// silently misattributing Yarn/Bun monorepos to Npm.
+static ALL_PACKAGE_MANAGERS: [PackageManager; 4] = [
+    PackageManager::Pnpm,
+    PackageManager::Yarn,
+    PackageManager::Bun,
+    PackageManager::Npm,
+];
+
 #[derive(Debug)]
 struct ShimArgs {
     cwd: PathBuf,
     skip_infer: bool,
+    shim_debug: bool,
     remaining_turbo_args: Vec<String>,
     forwarded_args: Vec<String>,
 }
 
 impl ShimArgs {
     pub fn parse() -> Result<Self> {
+        Self::parse_args(env::args().skip(1))
+    }
+
+    fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
         let mut found_cwd_flag = false;
         let mut cwd: Option<PathBuf> = None;
         let mut skip_infer = false;
+        let mut shim_debug = env::var(TURBO_SHIM_DEBUG_ENV_VAR).is_ok();
         let mut remaining_turbo_args = Vec::new();
         let mut forwarded_args = Vec::new();
         let mut is_forwarded_args = false;
-        let args = env::args().skip(1);
         for arg in args {
             // We've seen a `--` and therefore we do no parsing
             if is_forwarded_args {
                 forwarded_args.push(arg);
             } else if arg == "--skip-infer" {
                 skip_infer = true;
+            } else if let Some(value) = arg.strip_prefix("--skip-infer=") {
+                skip_infer = value
+                    .parse()
+                    .map_err(|_| anyhow!("`--skip-infer` must be `true` or `false`"))?;
+            } else if arg == "--shim-debug" {
+                shim_debug = true;
             } else if arg == "--" {
                 // If we've hit `--` we've reached the args forwarded to tasks.
                 is_forwarded_args = true;
@@ -67,6 +103,11 @@ impl ShimArgs {
                 }
                 // If we see a `--cwd` we expect the next arg to be a path.
                 found_cwd_flag = true
+            } else if let Some(value) = arg.strip_prefix("--cwd=") {
+                if cwd.is_some() {
+                    return Err(anyhow!("cannot have multiple `--cwd` flags in command"));
+                }
+                cwd = Some(value.into());
             } else {
                 remaining_turbo_args.push(arg);
             }
@@ -84,6 +125,7 @@ impl ShimArgs {
             Ok(ShimArgs {
                 cwd,
                 skip_infer,
+                shim_debug,
                 remaining_turbo_args,
                 forwarded_args,
             })
@@ -96,6 +138,25 @@ impl ShimArgs {
             .iter()
             .any(|arg| TURBO_PURE_OUTPUT_ARGS.contains(&arg.as_str()))
     }
+
+    // returns true if the shim debug trace should be printed: opted in via
+    // `--shim-debug`/`TURBO_SHIM_DEBUG`, and suppressed whenever pure JSON
+    // output is requested so scripts parsing stdout/stderr stay clean.
+    pub fn should_print_shim_debug(&self) -> bool {
+        self.shim_debug && !self.has_json_flags()
+    }
+}
+
+// Prints a shim debug trace line to stderr, only when `shim_debug` is set.
+// Implemented as a macro (rather than a function taking a formatted
+// `String`) so the message itself is only built when debug tracing is
+// enabled, keeping the default no-debug path allocation-free.
+macro_rules! shim_debug {
+    ($enabled:expr, $($arg:tt)*) => {
+        if $enabled {
+            eprintln!("[shim-debug] {}", format!($($arg)*));
+        }
+    };
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -124,25 +185,41 @@ impl RepoState {
     ///
     /// returns: Result<RepoState, Error>
     pub fn infer(current_dir: &Path) -> Result<Self> {
+        Self::infer_with_debug(current_dir, false)
+    }
+
+    fn infer_with_debug(current_dir: &Path, debug: bool) -> Result<Self> {
         // First we look for a `turbo.json`. This iterator returns the first ancestor
         // that contains a `turbo.json` file.
-        let root_path = current_dir
-            .ancestors()
-            .find(|p| fs::metadata(p.join(TURBO_JSON)).is_ok());
+        let mut root_path = None;
+        for ancestor in current_dir.ancestors() {
+            if fs::metadata(ancestor.join(TURBO_JSON)).is_ok() {
+                shim_debug!(
+                    debug,
+                    "checked {} - found {}, stopping here",
+                    ancestor.display(),
+                    TURBO_JSON
+                );
+                root_path = Some(ancestor);
+                break;
+            }
+            shim_debug!(debug, "checked {} - no {}", ancestor.display(), TURBO_JSON);
+        }
 
         // If that directory exists, then we figure out if there are workspaces defined
         // in it NOTE: This may change with multiple `turbo.json` files
         if let Some(root_path) = root_path {
-            let pnpm = PackageManager::Pnpm;
-            let npm = PackageManager::Npm;
-            let is_workspace = pnpm.get_workspace_globs(root_path).is_ok()
-                || npm.get_workspace_globs(root_path).is_ok();
+            let package_manager = ALL_PACKAGE_MANAGERS
+                .iter()
+                .find(|package_manager| package_manager.get_workspace_globs(root_path).is_ok());
+            shim_debug!(debug, "detected package manager: {:?}", package_manager);
 
-            let mode = if is_workspace {
+            let mode = if package_manager.is_some() {
                 RepoMode::MultiPackage
             } else {
                 RepoMode::SinglePackage
             };
+            shim_debug!(debug, "resolved mode: {:?}", mode);
 
             return Ok(Self {
                 root: root_path.to_path_buf(),
@@ -159,16 +236,18 @@ impl RepoState {
         // We loop through these directories and see if there are workspaces defined in
         // them, either in the `package.json` or `pnm-workspaces.yml`
         for dir in potential_roots {
+            shim_debug!(debug, "checked {} - found package.json", dir.display());
             if first_package_json_dir.is_none() {
                 first_package_json_dir = Some(dir)
             }
 
-            let pnpm = PackageManager::Pnpm;
-            let npm = PackageManager::Npm;
-            let is_workspace =
-                pnpm.get_workspace_globs(dir).is_ok() || npm.get_workspace_globs(dir).is_ok();
+            let package_manager = ALL_PACKAGE_MANAGERS
+                .iter()
+                .find(|package_manager| package_manager.get_workspace_globs(dir).is_ok());
+            shim_debug!(debug, "detected package manager: {:?}", package_manager);
 
-            if is_workspace {
+            if package_manager.is_some() {
+                shim_debug!(debug, "resolved mode: MultiPackage");
                 return Ok(Self {
                     root: dir.to_path_buf(),
                     mode: RepoMode::MultiPackage,
@@ -186,6 +265,11 @@ impl RepoState {
                 )
             })?
             .to_path_buf();
+        shim_debug!(
+            debug,
+            "no workspace found, falling back to {} in SinglePackage mode",
+            root.display()
+        );
 
         Ok(Self {
             root,
@@ -204,6 +288,7 @@ impl RepoState {
     ///
     /// returns: Result<i32, Error>
     fn run_correct_turbo(self, shim_args: ShimArgs) -> Result<Payload> {
+        let debug = shim_args.should_print_shim_debug();
         let local_turbo_path = self.root.join("node_modules").join(".bin").join({
             #[cfg(windows)]
             {
@@ -214,10 +299,16 @@ impl RepoState {
                 "turbo"
             }
         });
+        shim_debug!(
+            debug,
+            "resolved local_turbo_path: {}",
+            local_turbo_path.display()
+        );
 
-        if should_run_current_turbo(&local_turbo_path)? {
+        if should_run_current_turbo(&local_turbo_path, debug)? {
             cli::run(Some(self))
         } else {
+            self.warn_on_version_skew();
             let canonical_local_turbo = local_turbo_path.canonicalize()?;
             // Otherwise we spawn the local turbo process.
             Ok(Payload::Rust(
@@ -226,7 +317,7 @@ impl RepoState {
         }
     }
 
-    fn local_turbo_supports_skip_infer(&self) -> Result<bool> {
+    fn local_turbo_version(&self) -> Result<Version> {
         let local_turbo_package_path = self
             .root
             .join("node_modules")
@@ -234,11 +325,49 @@ impl RepoState {
             .join("package.json");
         let package_json: PackageJson =
             serde_json::from_reader(File::open(local_turbo_package_path)?)?;
-        let version = Version::from_str(&package_json.version)?;
+        Ok(Version::from_str(&package_json.version)?)
+    }
+
+    fn local_turbo_supports_skip_infer(&self) -> Result<bool> {
+        let version = self.local_turbo_version()?;
         let skip_infer_versions = VersionReq::parse(SUPPORTS_SKIP_INFER_SEMVER).unwrap();
         Ok(skip_infer_versions.matches(&version))
     }
 
+    /// Compares the local turbo version against the currently running
+    /// global turbo and emits a warning to stderr when they've drifted
+    /// apart by more minor versions than allowed (configurable via
+    /// `TURBO_VERSION_SKEW_MINOR_RANGE`, see `allowed_minor_version_skew`),
+    /// or at all across major versions. Best-effort: any failure to read or
+    /// parse the local version is silently ignored, since this is purely
+    /// advisory and shouldn't block delegation.
+    fn warn_on_version_skew(&self) {
+        if env::var(TURBO_IGNORE_VERSION_SKEW_ENV_VAR).is_ok() {
+            return;
+        }
+
+        let (Ok(local_version), Ok(global_version)) =
+            (self.local_turbo_version(), Version::parse(get_version()))
+        else {
+            return;
+        };
+
+        if version_skew_exceeds_range(
+            &global_version,
+            &local_version,
+            allowed_minor_version_skew(),
+        ) {
+            eprintln!(
+                "WARNING: the global turbo version ({global}) is running this repo, but the \
+                 repo is pinned to turbo {local}. This skew can cause unexpected behavior. Set \
+                 `{env_var}=1` to silence this warning.",
+                global = global_version,
+                local = local_version,
+                env_var = TURBO_IGNORE_VERSION_SKEW_ENV_VAR,
+            );
+        }
+    }
+
     fn spawn_local_turbo(&self, local_turbo_path: &Path, mut shim_args: ShimArgs) -> Result<i32> {
         println!(
             "Running local turbo binary in {}\n",
@@ -264,30 +393,94 @@ impl RepoState {
         raw_args.push("--".to_string());
         raw_args.append(&mut shim_args.forwarded_args);
 
+        // `remaining_turbo_args` has been drained into `raw_args` above, so the
+        // pure-output check looks at the turbo args in `raw_args` (everything
+        // before the `--` boundary) rather than calling `has_json_flags()`.
+        shim_debug!(
+            shim_args.shim_debug
+                && !raw_args
+                    .iter()
+                    .take_while(|arg| arg.as_str() != "--")
+                    .any(|arg| TURBO_PURE_OUTPUT_ARGS.contains(&arg.as_str())),
+            "assembled raw_args for local turbo: {:?}",
+            raw_args
+        );
+
         // We spawn a process that executes the local turbo
         // that we've found in node_modules/.bin/turbo.
         let mut command = process::Command::new(local_turbo_path)
             .args(&raw_args)
             .current_dir(cwd)
+            .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
             .spawn()
             .expect("Failed to execute turbo.");
 
-        Ok(command.wait()?.code().unwrap_or(2))
+        Ok(exit_code_for_status(command.wait()?))
     }
 }
 
 /// If the local turbo path doesn't exist or if we are local turbo, then we go
 /// ahead and run the Go code linked in the current binary.
-fn should_run_current_turbo(local_turbo_path: &Path) -> Result<bool> {
+fn should_run_current_turbo(local_turbo_path: &Path, debug: bool) -> Result<bool> {
     // Note we must check if local_turbo_path exists before we
     // canonicalize the path, otherwise we'll get an error.
     if !local_turbo_path.exists() {
+        shim_debug!(debug, "local turbo does not exist, running current turbo");
         return Ok(true);
     }
 
-    Ok(local_turbo_path.canonicalize()? == current_exe()?.canonicalize()?)
+    let is_same_binary = local_turbo_path.canonicalize()? == current_exe()?.canonicalize()?;
+    shim_debug!(
+        debug,
+        "local turbo canonicalized path matches current binary: {is_same_binary}"
+    );
+    Ok(is_same_binary)
+}
+
+/// Reproduces a child process's exit status as the exit code turbo itself
+/// should return. Normal exits pass their code straight through; on Unix, a
+/// child killed by a signal has no exit code, so we fall back to the
+/// conventional `128 + signal` so the shim's own exit code still tells the
+/// caller which signal ended the process.
+fn exit_code_for_status(status: process::ExitStatus) -> i32 {
+    if let Some(code) = status.code() {
+        return code;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+
+    2
+}
+
+/// Returns `true` if `global` and `local` have drifted far enough apart that
+/// a user should be warned before delegating: any major version mismatch, or
+/// a minor version gap wider than `allowed_minor_skew` within the same major
+/// version.
+fn version_skew_exceeds_range(global: &Version, local: &Version, allowed_minor_skew: u64) -> bool {
+    if global.major != local.major {
+        return true;
+    }
+
+    global.minor.abs_diff(local.minor) > allowed_minor_skew
+}
+
+/// Reads `TURBO_VERSION_SKEW_MINOR_RANGE` to let users tune how many minor
+/// versions the global and local turbo may drift apart before
+/// `warn_on_version_skew` fires. Falls back to
+/// `DEFAULT_ALLOWED_MINOR_VERSION_SKEW` when unset or unparseable.
+fn allowed_minor_version_skew() -> u64 {
+    env::var(TURBO_VERSION_SKEW_MINOR_RANGE_ENV_VAR)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_ALLOWED_MINOR_VERSION_SKEW)
 }
 
 /// Checks for `TURBO_BINARY_PATH` variable. If it is set,
@@ -334,11 +527,11 @@ pub fn run() -> Result<Payload> {
     // it to execute local turbo. We simply use it to set the `--single-package`
     // and `--cwd` flags.
     if is_turbo_binary_path_set() {
-        let repo_state = RepoState::infer(&args.cwd)?;
+        let repo_state = RepoState::infer_with_debug(&args.cwd, args.should_print_shim_debug())?;
         return cli::run(Some(repo_state));
     }
 
-    match RepoState::infer(&args.cwd) {
+    match RepoState::infer_with_debug(&args.cwd, args.should_print_shim_debug()) {
         Ok(repo_state) => repo_state.run_correct_turbo(args),
         Err(err) => {
             // If we cannot infer, we still run global turbo. This allows for global
@@ -366,4 +559,234 @@ mod test {
         assert!(req.matches(&new));
         assert!(!req.matches(&old));
     }
-}
\ No newline at end of file
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn write_fixture(root: &Path, files: &[(&str, &str)]) {
+        fs::write(root.join("turbo.json"), "{}").unwrap();
+        for (path, contents) in files {
+            let full_path = root.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+            fs::write(full_path, contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_infer_detects_pnpm_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            &[
+                ("package.json", r#"{"name": "root"}"#),
+                ("pnpm-workspace.yaml", "packages:\n  - \"packages/*\"\n"),
+            ],
+        );
+        let repo_state = RepoState::infer(dir.path()).unwrap();
+        assert_eq!(repo_state.mode, RepoMode::MultiPackage);
+    }
+
+    #[test]
+    fn test_infer_detects_npm_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            &[(
+                "package.json",
+                r#"{"name": "root", "workspaces": ["packages/*"]}"#,
+            )],
+        );
+        let repo_state = RepoState::infer(dir.path()).unwrap();
+        assert_eq!(repo_state.mode, RepoMode::MultiPackage);
+    }
+
+    #[test]
+    fn test_infer_detects_yarn_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            &[
+                (
+                    "package.json",
+                    r#"{"name": "root", "workspaces": ["packages/*"]}"#,
+                ),
+                ("yarn.lock", ""),
+            ],
+        );
+
+        // Assert directly against `PackageManager::Yarn`, not just the
+        // overall `RepoState::infer` mode: since `ALL_PACKAGE_MANAGERS`
+        // iterates with `.find()`, a broken/unimplemented Yarn matcher could
+        // still report `MultiPackage` if a manager probed earlier (or the
+        // generic `Npm` fallback) happens to recognize the same
+        // `workspaces` field.
+        assert!(PackageManager::Yarn
+            .get_workspace_globs(dir.path())
+            .is_ok());
+
+        let repo_state = RepoState::infer(dir.path()).unwrap();
+        assert_eq!(repo_state.mode, RepoMode::MultiPackage);
+    }
+
+    #[test]
+    fn test_infer_detects_bun_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            &[
+                (
+                    "package.json",
+                    r#"{"name": "root", "workspaces": ["packages/*"]}"#,
+                ),
+                ("bun.lockb", ""),
+            ],
+        );
+
+        // See the comment in `test_infer_detects_yarn_workspace` above:
+        // assert directly against `PackageManager::Bun` so this test fails
+        // if Bun's matcher itself is broken, not just if every matcher in
+        // `ALL_PACKAGE_MANAGERS` happens to be.
+        assert!(PackageManager::Bun.get_workspace_globs(dir.path()).is_ok());
+
+        let repo_state = RepoState::infer(dir.path()).unwrap();
+        assert_eq!(repo_state.mode, RepoMode::MultiPackage);
+    }
+
+    #[test]
+    fn test_version_skew_exceeds_range() {
+        let global = Version::parse("1.7.0").unwrap();
+
+        assert!(!version_skew_exceeds_range(
+            &global,
+            &Version::parse("1.7.0").unwrap(),
+            5
+        ));
+        assert!(!version_skew_exceeds_range(
+            &global,
+            &Version::parse("1.3.0").unwrap(),
+            5
+        ));
+        assert!(version_skew_exceeds_range(
+            &global,
+            &Version::parse("1.1.0").unwrap(),
+            5
+        ));
+        assert!(version_skew_exceeds_range(
+            &global,
+            &Version::parse("2.0.0").unwrap(),
+            5
+        ));
+    }
+
+    #[test]
+    fn test_version_skew_exceeds_range_configurable_threshold() {
+        let global = Version::parse("1.7.0").unwrap();
+        let local = Version::parse("1.1.0").unwrap();
+
+        // 6 minor versions apart: within a widened range, but not the default.
+        assert!(version_skew_exceeds_range(&global, &local, 5));
+        assert!(!version_skew_exceeds_range(&global, &local, 10));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_exit_code_for_status_normal_exit() {
+        use std::process::Command;
+        let status = Command::new("sh").args(["-c", "exit 3"]).status().unwrap();
+        assert_eq!(exit_code_for_status(status), 3);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_exit_code_for_status_signal() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+        // SIGKILL is signal 9, so we expect the conventional 128 + 9 = 137.
+        let status = ExitStatus::from_raw(9);
+        assert_eq!(exit_code_for_status(status), 137);
+    }
+
+    #[test]
+    fn test_infer_single_package_without_workspaces() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(dir.path(), &[("package.json", r#"{"name": "root"}"#)]);
+        let repo_state = RepoState::infer(dir.path()).unwrap();
+        assert_eq!(repo_state.mode, RepoMode::SinglePackage);
+    }
+
+    #[test]
+    fn test_parse_cwd_equals_form() {
+        let args = ShimArgs::parse_args(to_args(&["--cwd=."])).unwrap();
+        assert_eq!(args.cwd, PathBuf::from("."));
+        assert!(args.remaining_turbo_args.is_empty());
+
+        let args = ShimArgs::parse_args(to_args(&["--cwd=../foo"])).unwrap();
+        assert_eq!(args.cwd, PathBuf::from("../foo"));
+        assert!(args.remaining_turbo_args.is_empty());
+    }
+
+    #[test]
+    fn test_parse_cwd_space_form() {
+        let args = ShimArgs::parse_args(to_args(&["--cwd", "../foo"])).unwrap();
+        assert_eq!(args.cwd, PathBuf::from("../foo"));
+        assert!(args.remaining_turbo_args.is_empty());
+    }
+
+    #[test]
+    fn test_parse_cwd_rejects_duplicate_flags() {
+        assert!(ShimArgs::parse_args(to_args(&["--cwd=.", "--cwd=.."])).is_err());
+        assert!(ShimArgs::parse_args(to_args(&["--cwd", ".", "--cwd=.."])).is_err());
+        assert!(ShimArgs::parse_args(to_args(&["--cwd=.", "--cwd", ".."])).is_err());
+    }
+
+    #[test]
+    fn test_parse_skip_infer_equals_form() {
+        let args = ShimArgs::parse_args(to_args(&["--skip-infer=true"])).unwrap();
+        assert!(args.skip_infer);
+
+        let args = ShimArgs::parse_args(to_args(&["--skip-infer=false"])).unwrap();
+        assert!(!args.skip_infer);
+
+        assert!(ShimArgs::parse_args(to_args(&["--skip-infer=nope"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_mixed_equals_and_space_forms() {
+        let args = ShimArgs::parse_args(to_args(&[
+            "--cwd=../foo",
+            "--skip-infer",
+            "build",
+            "--",
+            "--forwarded",
+        ]))
+        .unwrap();
+        assert_eq!(args.cwd, PathBuf::from("../foo"));
+        assert!(args.skip_infer);
+        assert_eq!(args.remaining_turbo_args, vec!["build".to_string()]);
+        assert_eq!(args.forwarded_args, vec!["--forwarded".to_string()]);
+    }
+
+    #[test]
+    fn test_equals_form_never_leaks_into_remaining_args() {
+        let args = ShimArgs::parse_args(to_args(&["--cwd=.", "--skip-infer=true", "run"])).unwrap();
+        assert_eq!(args.remaining_turbo_args, vec!["run".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_shim_debug_flag() {
+        let args = ShimArgs::parse_args(to_args(&["--shim-debug", "run"])).unwrap();
+        assert!(args.shim_debug);
+        assert_eq!(args.remaining_turbo_args, vec!["run".to_string()]);
+    }
+
+    #[test]
+    fn test_shim_debug_suppressed_with_json_flags() {
+        let args = ShimArgs::parse_args(to_args(&["--shim-debug", "--json"])).unwrap();
+        assert!(args.shim_debug);
+        assert!(args.has_json_flags());
+        assert!(!args.should_print_shim_debug());
+    }
+}